@@ -87,7 +87,8 @@
 //! ```
 
 use std::borrow::Borrow;
-use std::fmt::{Result, Write};
+use std::ffi::OsStr;
+use std::fmt::{Formatter, Result, Write};
 
 /// A helper function to show bytes without explicitly creating a Printer struct.
 ///
@@ -100,6 +101,19 @@ where
     Printer::new(QuoteStyle::Double).into_string(bytes)
 }
 
+/// A helper function to show an `OsStr` without explicitly creating a Printer struct.
+///
+/// `show_os_str(os_str)` is equivalent to `Printer::new(QuoteStyle::Double).display_os_str(os_str)`.
+///
+/// Unlike `show_bytes`, this works portably on both Unix and Windows: on Unix
+/// it displays the `OsStr`'s raw bytes, and on Windows it decodes the
+/// WTF-8-encoded UTF-16 code units, rendering any unpaired surrogate (which
+/// can't occur in valid UTF-8 and so has no byte-oriented equivalent) as a
+/// `\u{XXXX}` escape.
+pub fn show_os_str(os_str: &OsStr) -> String {
+    Printer::new(QuoteStyle::Double).display_os_str(os_str)
+}
+
 /// Maintains an internal state describing how to display a byte array.
 ///
 /// It can write to an arbitrary `std::fmt::Write` implementation using the
@@ -107,6 +121,12 @@ where
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct Printer {
     quote_style: QuoteStyle,
+    escape_style: EscapeStyle,
+    decode_utf8: bool,
+    quote: u8,
+    escape: u8,
+    double_quote: bool,
+    quote_policy: QuotePolicy,
 }
 
 /// Indicates how, if at all, the printer should quote the byte string.
@@ -125,6 +145,52 @@ pub enum QuoteStyle {
     Double,
 }
 
+/// Indicates how the printer should escape non-graphic bytes.
+///
+/// An escape style is chosen by passing an `EscapeStyle` to `Printer::with_escape_style`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum EscapeStyle {
+    /// Indicates that the printer should use short, C-style escapes for the
+    /// common control bytes (`\n`, `\r`, `\t`, `\0`), falling back to `\xNN`
+    /// for any other non-graphic byte.
+    CStyle,
+    /// Indicates that the printer should always escape non-graphic bytes as
+    /// `\xNN`, even when a shorter C-style escape exists.
+    HexOnly,
+}
+
+/// Returns `EscapeStyle::CStyle`.
+impl Default for EscapeStyle {
+    fn default() -> Self {
+        Self::CStyle
+    }
+}
+
+/// Indicates when the printer should surround its output with quotes.
+///
+/// Only meaningful when `quote_style` isn't `QuoteStyle::None`. Modeled after
+/// csv-core's `QuoteStyle`, which adds quotes to a field only when the field
+/// would otherwise be ambiguous. Chosen via `Printer::with_quote_policy`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum QuotePolicy {
+    /// Always wrap the output in quotes.
+    Always,
+    /// Only wrap the output in quotes when the printer escapes at least one
+    /// byte of the input: the quote byte itself, the escape byte (whether or
+    /// not `double_quote` is set), or a non-graphic byte. With `decode_utf8`
+    /// enabled, bytes written through unescaped as part of a decoded
+    /// character (such as a space or a printable non-ASCII character) don't
+    /// force quoting.
+    Necessary,
+}
+
+/// Returns `QuotePolicy::Always`.
+impl Default for QuotePolicy {
+    fn default() -> Self {
+        Self::Always
+    }
+}
+
 /// Returns `Quotes::None`.
 impl Default for QuoteStyle {
     fn default() -> Self {
@@ -139,14 +205,183 @@ impl Default for Printer {
     fn default() -> Self {
         Self {
             quote_style: QuoteStyle::None,
+            escape_style: EscapeStyle::default(),
+            decode_utf8: false,
+            quote: b'"',
+            escape: b'\\',
+            double_quote: false,
+            quote_policy: QuotePolicy::default(),
+        }
+    }
+}
+
+/// A small, stack-allocated buffer holding the bytes of a single escaped
+/// output unit: a literal character, an escape sequence like `\n` or
+/// `\xNN`, or the UTF-8 encoding of a decoded scalar value. None of these
+/// are ever longer than 4 bytes, so no heap allocation is needed.
+///
+/// Because this carries no reference to a particular writer, it's shared
+/// between the `std::fmt::Write` path (`write_to`) and the `std::io::Write`
+/// path (`write_to_io`), keeping their escaping decisions identical.
+struct Chunk {
+    buf: [u8; 4],
+    len: u8,
+}
+
+impl Chunk {
+    fn from_char(ch: char) -> Self {
+        let mut buf = [0u8; 4];
+        let len = ch.encode_utf8(&mut buf).len() as u8;
+        Self { buf, len }
+    }
+
+    /// Builds a 2-byte chunk, used for two-character escapes like `\n` or a
+    /// doubled quote (`""`). Both bytes are assumed to be ASCII, which holds
+    /// for the `quote` and `escape` bytes a `Printer` is configured with.
+    fn from_two(a: u8, b: u8) -> Self {
+        Self {
+            buf: [a, b, 0, 0],
+            len: 2,
         }
     }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.buf[..self.len as usize])
+            .expect("Chunk bytes are always valid utf8 by construction")
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len as usize]
+    }
+}
+
+/// An error returned by `Printer::unescape` when its input isn't valid
+/// escaped output produced by a printer with matching settings.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ParseError {
+    /// The byte offset into the input string at which the problem was found.
+    pub offset: usize,
+    kind: ParseErrorKind,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ParseErrorKind {
+    MissingOpeningQuote,
+    MissingClosingQuote,
+    TrailingBytesAfterClosingQuote,
+    UnexpectedEnd,
+    InvalidHexDigit,
+    UnknownEscape,
+}
+
+impl ParseError {
+    fn new(offset: usize, kind: ParseErrorKind) -> Self {
+        Self { offset, kind }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let message = match self.kind {
+            ParseErrorKind::MissingOpeningQuote => "missing opening quote",
+            ParseErrorKind::MissingClosingQuote => "missing closing quote",
+            ParseErrorKind::TrailingBytesAfterClosingQuote => {
+                "unexpected bytes after closing quote"
+            }
+            ParseErrorKind::UnexpectedEnd => "unexpected end of input inside an escape sequence",
+            ParseErrorKind::InvalidHexDigit => "invalid hex digit in \\xNN escape",
+            ParseErrorKind::UnknownEscape => "unknown escape sequence",
+        };
+        write!(f, "{} at byte offset {}", message, self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn hex_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
 }
 
 impl Printer {
     /// Returns a new Printer with the chosen quoting style.
     pub fn new(quote_style: QuoteStyle) -> Self {
-        Self { quote_style }
+        let quote = match quote_style {
+            QuoteStyle::None | QuoteStyle::Double => b'"',
+            QuoteStyle::Single => b'\'',
+        };
+
+        Self {
+            quote_style,
+            escape_style: EscapeStyle::default(),
+            decode_utf8: false,
+            quote,
+            escape: b'\\',
+            double_quote: false,
+            quote_policy: QuotePolicy::default(),
+        }
+    }
+
+    /// Returns this printer with a custom quote byte, overriding the default
+    /// implied by its `QuoteStyle` (`'` for `Single`, `"` for `Double`). Must
+    /// be an ASCII byte. Has no effect when `quote_style` is `QuoteStyle::None`.
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Returns this printer with a custom escape byte. Must be an ASCII
+    /// byte. Defaults to `\`.
+    pub fn with_escape(mut self, escape: u8) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// Returns this printer with `double_quote` enabled or disabled.
+    ///
+    /// When enabled, an embedded quote byte is escaped by doubling it (e.g.
+    /// `""`) rather than by prefixing it with the escape byte (e.g. `\"`).
+    /// Defaults to `false`.
+    pub fn with_double_quote(mut self, double_quote: bool) -> Self {
+        self.double_quote = double_quote;
+        self
+    }
+
+    /// Returns this printer with the chosen quoting policy.
+    ///
+    /// Defaults to `QuotePolicy::Always`. Use `QuotePolicy::Necessary` to
+    /// only add quotes when some byte in the input would otherwise be
+    /// ambiguous, matching csv-core's rule for when a field needs quoting.
+    pub fn with_quote_policy(mut self, quote_policy: QuotePolicy) -> Self {
+        self.quote_policy = quote_policy;
+        self
+    }
+
+    /// Returns this printer with the chosen escape style.
+    ///
+    /// Defaults to `EscapeStyle::CStyle`, which escapes `\n`, `\r`, `\t`, and
+    /// `\0` with their short C-style escapes. Use `EscapeStyle::HexOnly` to
+    /// always escape non-graphic bytes as `\xNN`.
+    pub fn with_escape_style(mut self, escape_style: EscapeStyle) -> Self {
+        self.escape_style = escape_style;
+        self
+    }
+
+    /// Returns this printer with UTF-8 decoding enabled or disabled.
+    ///
+    /// When enabled, `write_to` runs an incremental UTF-8 decoder over the
+    /// byte sequence: complete, valid, non-control scalar values are written
+    /// directly as characters (except this printer's quote and escape bytes,
+    /// which are escaped as usual), while invalid or truncated sequences fall back
+    /// to `\xNN` escapes for exactly the offending bytes. Defaults to `false`,
+    /// which escapes every non-graphic byte independently.
+    pub fn with_decode_utf8(mut self, decode_utf8: bool) -> Self {
+        self.decode_utf8 = decode_utf8;
+        self
     }
 
     /// Writes the formatted bytes to an arbitrary `std::fmt::Write` implementation.
@@ -168,32 +403,410 @@ impl Printer {
         I::Item: Borrow<u8>,
         W: Write,
     {
-        match self.quote_style {
-            QuoteStyle::None => Ok(()),
-            QuoteStyle::Single => writer.write_char('\''),
-            QuoteStyle::Double => writer.write_char('"'),
-        }?;
-
-        for byte_borrow in bytes.into_iter() {
-            let byte = *byte_borrow.borrow();
-            match self.quote_style {
-                QuoteStyle::Single if byte == b'\'' => writer.write_str("\\'"),
-                QuoteStyle::Double if byte == b'"' => writer.write_str("\\\""),
-                _ if byte == b'\\' => writer.write_str("\\\\"),
-                _ if byte.is_ascii_graphic() => writer.write_char(byte as char),
-                _ => write!(writer, "\\x{:02x}", byte),
-            }?;
-        }
-
-        match self.quote_style {
-            QuoteStyle::None => Ok(()),
-            QuoteStyle::Single => writer.write_char('\''),
-            QuoteStyle::Double => writer.write_char('"'),
-        }?;
+        if self.quote_style != QuoteStyle::None && self.quote_policy == QuotePolicy::Necessary {
+            let buffered: Vec<u8> = bytes.into_iter().map(|b| *b.borrow()).collect();
+            let quote_needed = self.quote_needed(&buffered);
+
+            if quote_needed {
+                writer.write_char(self.quote as char)?;
+            }
+            self.write_body(buffered.into_iter(), writer)?;
+            if quote_needed {
+                writer.write_char(self.quote as char)?;
+            }
+
+            return Ok(());
+        }
+
+        if self.quote_style != QuoteStyle::None {
+            writer.write_char(self.quote as char)?;
+        }
+
+        self.write_body(bytes.into_iter(), writer)?;
+
+        if self.quote_style != QuoteStyle::None {
+            writer.write_char(self.quote as char)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the body of the output (everything between the opening and
+    /// closing quote, if any), dispatching to the UTF-8 decoder when enabled.
+    fn write_body<I, W>(&self, bytes: I, writer: &mut W) -> Result
+    where
+        I: Iterator,
+        I::Item: Borrow<u8>,
+        W: Write,
+    {
+        if self.decode_utf8 {
+            self.write_decoded(bytes, writer)
+        } else {
+            for byte_borrow in bytes {
+                writer.write_str(self.escape_byte(*byte_borrow.borrow()).as_str())?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Writes the formatted bytes directly to an arbitrary `std::io::Write`
+    /// implementation, bypassing the `std::fmt` machinery entirely.
+    ///
+    /// Because every byte this printer writes is valid ASCII or UTF-8, going
+    /// through `std::fmt::Write` (as `write_to` does) pays for UTF-8
+    /// revalidation and indirection that isn't needed here. This method
+    /// shares its escaping decisions with `write_to`, so the two produce
+    /// identical output; use this one to stream escaped output to a file,
+    /// socket, or `Vec<u8>` without materializing an intermediate `String`.
+    pub fn write_to_io<I, W>(&self, bytes: I, writer: &mut W) -> std::io::Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<u8>,
+        W: std::io::Write,
+    {
+        if self.quote_style != QuoteStyle::None && self.quote_policy == QuotePolicy::Necessary {
+            let buffered: Vec<u8> = bytes.into_iter().map(|b| *b.borrow()).collect();
+            let quote_needed = self.quote_needed(&buffered);
+
+            if quote_needed {
+                writer.write_all(&[self.quote])?;
+            }
+            self.write_body_io(buffered.into_iter(), writer)?;
+            if quote_needed {
+                writer.write_all(&[self.quote])?;
+            }
+
+            return Ok(());
+        }
+
+        if self.quote_style != QuoteStyle::None {
+            writer.write_all(&[self.quote])?;
+        }
+
+        self.write_body_io(bytes.into_iter(), writer)?;
+
+        if self.quote_style != QuoteStyle::None {
+            writer.write_all(&[self.quote])?;
+        }
+
+        Ok(())
+    }
+
+    /// The `std::io::Write` counterpart of `write_body`.
+    fn write_body_io<I, W>(&self, bytes: I, writer: &mut W) -> std::io::Result<()>
+    where
+        I: Iterator,
+        I::Item: Borrow<u8>,
+        W: std::io::Write,
+    {
+        if self.decode_utf8 {
+            self.write_decoded_io(bytes, writer)
+        } else {
+            for byte_borrow in bytes {
+                writer.write_all(self.escape_byte(*byte_borrow.borrow()).as_bytes())?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Returns whether any byte in `bytes` would force quoting under
+    /// `QuotePolicy::Necessary`.
+    ///
+    /// This renders `bytes` through `write_body` (the same code path
+    /// `write_to` uses for the actual output) and compares the result
+    /// against the original bytes: if nothing was escaped, the rendered
+    /// body is byte-for-byte identical to the input. Deciding this by
+    /// actually rendering, rather than consulting a static per-byte table,
+    /// keeps the decision in sync with `decode_utf8`: a valid multi-byte
+    /// UTF-8 sequence that decodes to a printable character passes through
+    /// unescaped, so it must not force quoting either.
+    fn quote_needed(&self, bytes: &[u8]) -> bool {
+        let mut rendered = String::new();
+        self.write_body(bytes.iter(), &mut rendered)
+            .expect("writing to a String is infallible");
+        rendered.into_bytes() != bytes
+    }
+
+    /// Decides how a single byte should be rendered, without writing
+    /// anything. Shared by `write_to` and `write_to_io` so the two paths
+    /// can never disagree on escaping.
+    fn escape_byte(&self, byte: u8) -> Chunk {
+        if self.quote_style != QuoteStyle::None && byte == self.quote {
+            return if self.double_quote {
+                Chunk::from_two(self.quote, self.quote)
+            } else {
+                Chunk::from_two(self.escape, self.quote)
+            };
+        }
+        if byte == self.escape {
+            return Chunk::from_two(self.escape, self.escape);
+        }
+
+        match self.escape_style {
+            EscapeStyle::CStyle if byte == b'\n' => Chunk::from_two(self.escape, b'n'),
+            EscapeStyle::CStyle if byte == b'\r' => Chunk::from_two(self.escape, b'r'),
+            EscapeStyle::CStyle if byte == b'\t' => Chunk::from_two(self.escape, b't'),
+            EscapeStyle::CStyle if byte == b'\0' => Chunk::from_two(self.escape, b'0'),
+            _ if byte.is_ascii_graphic() => Chunk::from_char(byte as char),
+            _ => {
+                const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+                Chunk {
+                    buf: [
+                        self.escape,
+                        b'x',
+                        HEX_DIGITS[(byte >> 4) as usize],
+                        HEX_DIGITS[(byte & 0x0f) as usize],
+                    ],
+                    len: 4,
+                }
+            }
+        }
+    }
+
+    /// Runs an incremental UTF-8 decoder over `bytes`, writing decoded
+    /// printable scalar values directly and falling back to `\xNN` escapes
+    /// for exactly the bytes of any invalid or truncated sequence.
+    fn write_decoded<I, W>(&self, mut bytes: I, writer: &mut W) -> Result
+    where
+        I: Iterator,
+        I::Item: Borrow<u8>,
+        W: Write,
+    {
+        let mut pending = [0u8; 4];
+        let mut pending_len = 0usize;
+
+        'outer: loop {
+            if pending_len == 0 {
+                pending[0] = match bytes.next() {
+                    Some(byte_borrow) => *byte_borrow.borrow(),
+                    None => break 'outer,
+                };
+                pending_len = 1;
+            }
+
+            loop {
+                match std::str::from_utf8(&pending[..pending_len]) {
+                    Ok(decoded) => {
+                        let ch = decoded
+                            .chars()
+                            .next()
+                            .expect("a non-empty utf8 str decodes to at least one char");
+                        // A decoded char that's also this printer's quote or
+                        // escape byte still needs the same special-casing it
+                        // would get as a raw byte, so route it through
+                        // `escape_byte` rather than writing it verbatim.
+                        let needs_escaping = ch.is_control()
+                            || (self.quote_style != QuoteStyle::None && pending[0] == self.quote)
+                            || pending[0] == self.escape;
+                        if needs_escaping {
+                            for &byte in &pending[..pending_len] {
+                                writer.write_str(self.escape_byte(byte).as_str())?;
+                            }
+                        } else {
+                            writer.write_str(Chunk::from_char(ch).as_str())?;
+                        }
+                        pending_len = 0;
+                        continue 'outer;
+                    }
+                    Err(err) if err.error_len().is_some() => {
+                        // The lead byte can't extend to a valid sequence at all;
+                        // emit it and resynchronize starting at the next byte.
+                        writer.write_str(self.escape_byte(pending[0]).as_str())?;
+                        pending.copy_within(1..pending_len, 0);
+                        pending_len -= 1;
+                        if pending_len == 0 {
+                            continue 'outer;
+                        }
+                    }
+                    Err(_) => {
+                        // A valid but incomplete prefix: pull another byte and retry.
+                        match bytes.next() {
+                            Some(byte_borrow) => {
+                                pending[pending_len] = *byte_borrow.borrow();
+                                pending_len += 1;
+                            }
+                            None => break 'outer,
+                        }
+                    }
+                }
+            }
+        }
+
+        for &byte in &pending[..pending_len] {
+            writer.write_str(self.escape_byte(byte).as_str())?;
+        }
 
         Ok(())
     }
 
+    /// The `std::io::Write` counterpart of `write_decoded`.
+    fn write_decoded_io<I, W>(&self, mut bytes: I, writer: &mut W) -> std::io::Result<()>
+    where
+        I: Iterator,
+        I::Item: Borrow<u8>,
+        W: std::io::Write,
+    {
+        let mut pending = [0u8; 4];
+        let mut pending_len = 0usize;
+
+        'outer: loop {
+            if pending_len == 0 {
+                pending[0] = match bytes.next() {
+                    Some(byte_borrow) => *byte_borrow.borrow(),
+                    None => break 'outer,
+                };
+                pending_len = 1;
+            }
+
+            loop {
+                match std::str::from_utf8(&pending[..pending_len]) {
+                    Ok(decoded) => {
+                        let ch = decoded
+                            .chars()
+                            .next()
+                            .expect("a non-empty utf8 str decodes to at least one char");
+                        let needs_escaping = ch.is_control()
+                            || (self.quote_style != QuoteStyle::None && pending[0] == self.quote)
+                            || pending[0] == self.escape;
+                        if needs_escaping {
+                            for &byte in &pending[..pending_len] {
+                                writer.write_all(self.escape_byte(byte).as_bytes())?;
+                            }
+                        } else {
+                            writer.write_all(Chunk::from_char(ch).as_bytes())?;
+                        }
+                        pending_len = 0;
+                        continue 'outer;
+                    }
+                    Err(err) if err.error_len().is_some() => {
+                        writer.write_all(self.escape_byte(pending[0]).as_bytes())?;
+                        pending.copy_within(1..pending_len, 0);
+                        pending_len -= 1;
+                        if pending_len == 0 {
+                            continue 'outer;
+                        }
+                    }
+                    Err(_) => match bytes.next() {
+                        Some(byte_borrow) => {
+                            pending[pending_len] = *byte_borrow.borrow();
+                            pending_len += 1;
+                        }
+                        None => break 'outer,
+                    },
+                }
+            }
+        }
+
+        for &byte in &pending[..pending_len] {
+            writer.write_all(self.escape_byte(byte).as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the formatted contents of an `OsStr` to an arbitrary
+    /// `std::fmt::Write` implementation.
+    ///
+    /// On Unix, `OsStr` is an arbitrary, possibly-invalid-UTF-8 byte
+    /// sequence, so this delegates straight to `write_to`. On Windows,
+    /// `OsStr` has no raw-bytes accessor (it's WTF-8-encoded UTF-16), so this
+    /// decodes its code units instead, escaping any unpaired surrogate or
+    /// non-ASCII control character as `\u{XXXX}`. This gives callers one
+    /// portable way to display process arguments, environment variables,
+    /// and paths without `#[cfg(unix)]` guards at every call site.
+    #[cfg(unix)]
+    pub fn write_os_str<W>(&self, os_str: &OsStr, writer: &mut W) -> Result
+    where
+        W: Write,
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        self.write_to(os_str.as_bytes(), writer)
+    }
+
+    /// Writes the formatted contents of an `OsStr` to an arbitrary
+    /// `std::fmt::Write` implementation.
+    ///
+    /// On Unix, `OsStr` is an arbitrary, possibly-invalid-UTF-8 byte
+    /// sequence, so this delegates straight to `write_to`. On Windows,
+    /// `OsStr` has no raw-bytes accessor (it's WTF-8-encoded UTF-16), so this
+    /// decodes its code units instead, escaping any unpaired surrogate or
+    /// non-ASCII control character as `\u{XXXX}`. This gives callers one
+    /// portable way to display process arguments, environment variables,
+    /// and paths without `#[cfg(unix)]` guards at every call site.
+    #[cfg(windows)]
+    pub fn write_os_str<W>(&self, os_str: &OsStr, writer: &mut W) -> Result
+    where
+        W: Write,
+    {
+        let quote_needed = match self.quote_style {
+            QuoteStyle::None => false,
+            _ => match self.quote_policy {
+                QuotePolicy::Always => true,
+                QuotePolicy::Necessary => self.os_str_quote_needed(os_str),
+            },
+        };
+
+        if quote_needed {
+            writer.write_char(self.quote as char)?;
+        }
+
+        self.write_os_str_body(os_str, writer)?;
+
+        if quote_needed {
+            writer.write_char(self.quote as char)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the body of an `OsStr`'s output (everything between the
+    /// opening and closing quote, if any). Shared by `write_os_str` and
+    /// `os_str_quote_needed` so the two can never disagree on escaping.
+    #[cfg(windows)]
+    fn write_os_str_body<W>(&self, os_str: &OsStr, writer: &mut W) -> Result
+    where
+        W: Write,
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        for unit in std::char::decode_utf16(os_str.encode_wide()) {
+            match unit {
+                Ok(ch) if ch.is_ascii() => writer.write_str(self.escape_byte(ch as u8).as_str())?,
+                // Non-ASCII control characters (e.g. U+0085 NEL) still need
+                // escaping, the same way write_decoded's needs_escaping check
+                // doesn't give ASCII-ness a pass on control chars either.
+                Ok(ch) if ch.is_control() => write!(writer, "\\u{{{:04x}}}", ch as u32)?,
+                Ok(ch) => writer.write_char(ch)?,
+                Err(err) => write!(writer, "\\u{{{:04x}}}", err.unpaired_surrogate())?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether `os_str` would force quoting under `QuotePolicy::Necessary`,
+    /// decided the same way `quote_needed` decides it for bytes: render the
+    /// body unquoted and check whether anything was actually escaped.
+    #[cfg(windows)]
+    fn os_str_quote_needed(&self, os_str: &OsStr) -> bool {
+        let mut rendered = String::new();
+        self.write_os_str_body(os_str, &mut rendered)
+            .expect("writing to a String is infallible");
+        rendered != os_str.to_string_lossy()
+    }
+
+    /// Returns a string displaying an `OsStr`. See `write_os_str` for details.
+    pub fn display_os_str(&self, os_str: &OsStr) -> String {
+        let mut output = String::new();
+
+        self.write_os_str(os_str, &mut output).expect(
+            "Writing to a string shouldn't fail, it uses infallible methods like String::push.",
+        );
+
+        output
+    }
+
     /// Returns a string displaying the bytes.
     pub fn into_string<I>(&self, bytes: I) -> String
     where
@@ -208,4 +821,357 @@ impl Printer {
 
         output
     }
+
+    /// Parses a string produced by this printer's `write_to`/`into_string`
+    /// back into the original bytes, returning a `ParseError` reporting the
+    /// byte offset of any malformed escape.
+    ///
+    /// This undoes quoting according to `quote_style`, `quote`, `escape`,
+    /// and `double_quote`, decodes `\xNN` hex escapes, and (when
+    /// `escape_style` is `EscapeStyle::CStyle`) decodes `\n`, `\r`, `\t`, and
+    /// `\0`. Any other byte, including the UTF-8 encoding of a decoded
+    /// character written by UTF-8 mode, is copied through unchanged, so the
+    /// round trip works regardless of whether `decode_utf8` is enabled.
+    pub fn unescape(&self, s: &str) -> std::result::Result<Vec<u8>, ParseError> {
+        let bytes = s.as_bytes();
+        let mut i = 0usize;
+
+        // Under `QuotePolicy::Always` every non-`None` quote style is always
+        // wrapped in quotes, but under `QuotePolicy::Necessary` the printer
+        // only quotes when the content actually needs it, so `s` may well be
+        // unquoted. A leading quote byte is only ever written because it
+        // opens a quoted string (the quote byte itself always forces
+        // quoting, see `quote_needed`), so sniffing for it tells us whether
+        // `s` is quoted at all rather than assuming it from `quote_style` alone.
+        let is_quoted = self.quote_style != QuoteStyle::None
+            && (self.quote_policy == QuotePolicy::Always || bytes.first() == Some(&self.quote));
+
+        if is_quoted {
+            if bytes.first() != Some(&self.quote) {
+                return Err(ParseError::new(0, ParseErrorKind::MissingOpeningQuote));
+            }
+            i = 1;
+        }
+
+        let mut output = Vec::new();
+
+        loop {
+            let byte = match bytes.get(i) {
+                Some(&byte) => byte,
+                None => {
+                    if is_quoted {
+                        return Err(ParseError::new(i, ParseErrorKind::MissingClosingQuote));
+                    }
+                    break;
+                }
+            };
+
+            if is_quoted && byte == self.quote {
+                if self.double_quote && bytes.get(i + 1) == Some(&self.quote) {
+                    output.push(self.quote);
+                    i += 2;
+                    continue;
+                }
+                i += 1;
+                break;
+            }
+
+            if byte == self.escape {
+                let escape_offset = i;
+                let next = bytes
+                    .get(i + 1)
+                    .copied()
+                    .ok_or_else(|| ParseError::new(escape_offset, ParseErrorKind::UnexpectedEnd))?;
+
+                match next {
+                    n if n == self.escape => {
+                        output.push(self.escape);
+                        i += 2;
+                    }
+                    n if is_quoted && n == self.quote => {
+                        output.push(self.quote);
+                        i += 2;
+                    }
+                    b'n' if self.escape_style == EscapeStyle::CStyle => {
+                        output.push(b'\n');
+                        i += 2;
+                    }
+                    b'r' if self.escape_style == EscapeStyle::CStyle => {
+                        output.push(b'\r');
+                        i += 2;
+                    }
+                    b't' if self.escape_style == EscapeStyle::CStyle => {
+                        output.push(b'\t');
+                        i += 2;
+                    }
+                    b'0' if self.escape_style == EscapeStyle::CStyle => {
+                        output.push(0);
+                        i += 2;
+                    }
+                    b'x' => {
+                        let digit = |offset: usize| {
+                            bytes
+                                .get(offset)
+                                .copied()
+                                .ok_or_else(|| {
+                                    ParseError::new(escape_offset, ParseErrorKind::UnexpectedEnd)
+                                })
+                                .and_then(|byte| {
+                                    hex_digit(byte).ok_or_else(|| {
+                                        ParseError::new(
+                                            escape_offset,
+                                            ParseErrorKind::InvalidHexDigit,
+                                        )
+                                    })
+                                })
+                        };
+                        let value = (digit(i + 2)? << 4) | digit(i + 3)?;
+                        output.push(value);
+                        i += 4;
+                    }
+                    _ => return Err(ParseError::new(escape_offset, ParseErrorKind::UnknownEscape)),
+                }
+                continue;
+            }
+
+            output.push(byte);
+            i += 1;
+        }
+
+        if i != bytes.len() {
+            return Err(ParseError::new(
+                i,
+                ParseErrorKind::TrailingBytesAfterClosingQuote,
+            ));
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn c_style_escapes_common_control_bytes() {
+        let printer = Printer::new(QuoteStyle::Double);
+        assert_eq!(
+            printer.into_string(*b"a\nb\rc\td\0e"),
+            "\"a\\nb\\rc\\td\\0e\""
+        );
+    }
+
+    #[test]
+    fn c_style_falls_back_to_hex_for_other_non_graphic_bytes() {
+        let printer = Printer::new(QuoteStyle::Double);
+        assert_eq!(printer.into_string([0x01, 0x7f, 0xff]), "\"\\x01\\x7f\\xff\"");
+    }
+
+    #[test]
+    fn hex_only_escapes_every_non_graphic_byte_as_hex() {
+        let printer = Printer::new(QuoteStyle::Double).with_escape_style(EscapeStyle::HexOnly);
+        assert_eq!(
+            printer.into_string(*b"a\nb\0"),
+            "\"a\\x0ab\\x00\""
+        );
+    }
+
+    #[test]
+    fn ascii_graphic_bytes_pass_through_unescaped() {
+        let printer = Printer::new(QuoteStyle::Double);
+        assert_eq!(printer.into_string(*b"Hello!"), "\"Hello!\"");
+    }
+
+    #[test]
+    fn decode_utf8_writes_valid_multibyte_sequences_directly() {
+        let printer = Printer::new(QuoteStyle::Double).with_decode_utf8(true);
+        assert_eq!(printer.into_string("café".bytes()), "\"café\"");
+    }
+
+    #[test]
+    fn decode_utf8_escapes_invalid_bytes() {
+        let printer = Printer::new(QuoteStyle::Double).with_decode_utf8(true);
+        assert_eq!(printer.into_string([0xff, 0xfe]), "\"\\xff\\xfe\"");
+    }
+
+    #[test]
+    fn decode_utf8_escapes_truncated_trailing_sequence() {
+        let printer = Printer::new(QuoteStyle::Double).with_decode_utf8(true);
+        // The leading byte of 'é' (0xc3 0xa9) with no continuation byte.
+        assert_eq!(printer.into_string([b'a', 0xc3]), "\"a\\xc3\"");
+    }
+
+    #[test]
+    fn decode_utf8_still_escapes_quote_and_escape_bytes() {
+        // Regression test: a decoded quote or escape byte must still be
+        // routed through escape_byte instead of being written verbatim,
+        // or the surrounding quoting breaks.
+        let printer = Printer::new(QuoteStyle::Double).with_decode_utf8(true);
+        assert_eq!(
+            printer.into_string(vec![b'\\', b'"', b'a']),
+            "\"\\\\\\\"a\""
+        );
+    }
+
+    #[test]
+    fn write_to_io_matches_write_to() {
+        let printer = Printer::new(QuoteStyle::Double);
+        let bytes = *b"Hello\n\0\xff, World!";
+
+        let mut io_output = Vec::new();
+        printer.write_to_io(bytes, &mut io_output).unwrap();
+
+        assert_eq!(String::from_utf8(io_output).unwrap(), printer.into_string(bytes));
+    }
+
+    #[test]
+    fn write_to_io_matches_write_to_with_decode_utf8() {
+        let printer = Printer::new(QuoteStyle::Double).with_decode_utf8(true);
+        let bytes: Vec<u8> = "café\n".bytes().collect();
+
+        let mut io_output = Vec::new();
+        printer.write_to_io(bytes.iter(), &mut io_output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(io_output).unwrap(),
+            printer.into_string(bytes.iter())
+        );
+    }
+
+    #[test]
+    fn custom_quote_and_escape_bytes() {
+        let printer = Printer::new(QuoteStyle::Double)
+            .with_quote(b'`')
+            .with_escape(b'%');
+        assert_eq!(printer.into_string(*b"a`b%c"), "`a%`b%%c`");
+    }
+
+    #[test]
+    fn double_quote_escapes_embedded_quote_by_doubling() {
+        let printer = Printer::new(QuoteStyle::Double).with_double_quote(true);
+        assert_eq!(printer.into_string(*b"a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn quote_policy_necessary_omits_quotes_when_unambiguous() {
+        let printer = Printer::new(QuoteStyle::Double).with_quote_policy(QuotePolicy::Necessary);
+        assert_eq!(printer.into_string(*b"hello"), "hello");
+    }
+
+    #[test]
+    fn quote_policy_necessary_quotes_when_ambiguous() {
+        let printer = Printer::new(QuoteStyle::Double).with_quote_policy(QuotePolicy::Necessary);
+        assert_eq!(printer.into_string(*b"hello\tworld"), "\"hello\\tworld\"");
+        assert_eq!(printer.into_string(*b"say\"hi\""), "\"say\\\"hi\\\"\"");
+    }
+
+    #[test]
+    fn quote_policy_necessary_with_decode_utf8_skips_quoting_printable_non_ascii() {
+        // Regression test: non-ASCII bytes shouldn't force quoting under
+        // decode_utf8 when they decode to printable characters that need no
+        // escaping at all.
+        let printer = Printer::new(QuoteStyle::Double)
+            .with_quote_policy(QuotePolicy::Necessary)
+            .with_decode_utf8(true);
+        assert_eq!(printer.into_string("café".bytes()), "café");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn display_os_str_matches_display_of_the_same_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let os_str = OsStr::from_bytes(b"hello\n\xff");
+        let printer = Printer::new(QuoteStyle::Double);
+
+        assert_eq!(
+            printer.display_os_str(os_str),
+            printer.into_string(os_str.as_bytes())
+        );
+    }
+
+    #[test]
+    fn show_os_str_matches_show_bytes() {
+        let os_str = OsStr::new("hello");
+        assert_eq!(show_os_str(os_str), show_bytes("hello".bytes()));
+    }
+
+    #[test]
+    fn unescape_undoes_into_string() {
+        let printer = Printer::new(QuoteStyle::Double);
+        let bytes = b"hello\n\0\xff, \"world\"!";
+        let escaped = printer.into_string(*bytes);
+        assert_eq!(printer.unescape(&escaped).unwrap(), bytes);
+    }
+
+    #[test]
+    fn unescape_reports_missing_opening_quote() {
+        let printer = Printer::new(QuoteStyle::Double);
+        let err = printer.unescape("no leading quote\"").unwrap_err();
+        assert_eq!(err.offset, 0);
+    }
+
+    #[test]
+    fn unescape_reports_unknown_escape() {
+        let printer = Printer::new(QuoteStyle::Double);
+        let err = printer.unescape("\"\\q\"").unwrap_err();
+        assert_eq!(err.offset, 1);
+    }
+
+    #[test]
+    fn unescape_round_trips_quote_policy_necessary_unquoted_output() {
+        // Regression test: QuotePolicy::Necessary can produce unquoted
+        // output, which unescape must accept rather than requiring an
+        // opening quote purely because quote_style isn't None.
+        let printer = Printer::new(QuoteStyle::Double).with_quote_policy(QuotePolicy::Necessary);
+        let bytes = b"hello";
+        let escaped = printer.into_string(*bytes);
+        assert_eq!(escaped, "hello");
+        assert_eq!(printer.unescape(&escaped).unwrap(), bytes);
+    }
+
+    #[test]
+    fn round_trip_matrix() {
+        let quote_styles = [QuoteStyle::None, QuoteStyle::Single, QuoteStyle::Double];
+        let escape_styles = [EscapeStyle::CStyle, EscapeStyle::HexOnly];
+        let quote_policies = [QuotePolicy::Always, QuotePolicy::Necessary];
+        let decode_utf8_settings = [false, true];
+
+        let samples: &[&[u8]] = &[
+            b"",
+            b"hello",
+            b"hello world",
+            b"quote\"and\\backslash'",
+            b"\n\r\t\0\x01\x7f\xff",
+            "café".as_bytes(),
+        ];
+
+        for &quote_style in &quote_styles {
+            for &escape_style in &escape_styles {
+                for &quote_policy in &quote_policies {
+                    for &decode_utf8 in &decode_utf8_settings {
+                        let printer = Printer::new(quote_style)
+                            .with_escape_style(escape_style)
+                            .with_quote_policy(quote_policy)
+                            .with_decode_utf8(decode_utf8);
+
+                        for &sample in samples {
+                            let escaped = printer.into_string(sample);
+                            let round_tripped = printer.unescape(&escaped).unwrap_or_else(|err| {
+                                panic!(
+                                    "unescape({:?}) failed for sample {:?} with \
+                                     quote_style={:?}, escape_style={:?}, \
+                                     quote_policy={:?}, decode_utf8={}: {}",
+                                    escaped, sample, quote_style, escape_style, quote_policy,
+                                    decode_utf8, err
+                                )
+                            });
+                            assert_eq!(round_tripped, sample);
+                        }
+                    }
+                }
+            }
+        }
+    }
 }